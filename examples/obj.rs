@@ -14,10 +14,12 @@ fn run() -> Result<()> {
     if let Some(uri) = args.next() {
         let obj = throw!(load(&uri, ObjScene));
         // print contents of obj file
-        for mesh in &obj.nodes {
-            println!("{}", mesh.1);
-            for v in &mesh.0.verts {
-                println!("\t{},{},{}", v.x, v.y, v.z);
+        for node in &obj.nodes {
+            println!("{}", node.name);
+            for mesh in &node.meshes {
+                for v in &mesh.verts {
+                    println!("\t{},{},{}", v.x, v.y, v.z);
+                }
             }
         }
     } else {
@@ -0,0 +1,241 @@
+//! The `.bin` chunk archive format
+//!
+//! A `.bin` archive is a sequence of length-prefixed chunks, each identified by a `u128` id:
+//!
+//! ```text
+//! [id: u128][length: u64][body: length bytes] ...
+//! ```
+//!
+//! Archives written with [`ArchiveWriter::finish`] additionally carry an index footer: a sorted
+//! table of `(id, offset, length)` entries stored in the implicit binary-search-tree ("Eytzinger")
+//! array layout, followed by a fixed-size trailer recording where that table starts. This turns
+//! looking up one chunk out of many into an O(log n) seek-and-compare walk instead of a linear
+//! scan of every chunk header. Archives without a footer (or written by something else) still
+//! work; [`Path::reader`](crate::Path::reader) falls back to scanning chunks in order.
+
+use std::{
+    fs::File,
+    io::{self, Read, Seek, SeekFrom, Write},
+};
+
+use reerror::Result;
+
+const FOOTER_MAGIC: u32 = 0xB14B_5711;
+const ENTRY_LEN: usize = 16 + 8 + 8; // id + offset + length
+const TRAILER_LEN: usize = 8 + 8 + 4; // table_offset + entry_count + magic
+
+/// Reads chunks sequentially from a `.bin` archive, advancing past each chunk's body as it's read
+pub(crate) trait BinRead {
+    fn chunk(&mut self) -> Result<Chunk>;
+}
+
+impl BinRead for File {
+    fn chunk(&mut self) -> Result<Chunk> {
+        let mut id_buf = [0u8; 16];
+        self.read_exact(&mut id_buf)?;
+        let id = u128::from_le_bytes(id_buf);
+
+        let mut len_buf = [0u8; 8];
+        self.read_exact(&mut len_buf)?;
+        let length = u64::from_le_bytes(len_buf);
+
+        let offset = self.stream_position()?;
+        self.seek(SeekFrom::Current(length as i64))?;
+
+        Ok(Chunk {
+            file: self.try_clone()?,
+            offset,
+            length,
+            id,
+        })
+    }
+}
+
+/// One chunk header read from an archive, positioned to read its body
+pub(crate) struct Chunk {
+    file: File,
+    offset: u64,
+    length: u64,
+    id: u128,
+}
+
+impl Chunk {
+    pub(crate) fn id(&self) -> u128 {
+        self.id
+    }
+
+    /// A reader over just this chunk's body
+    pub(crate) fn read(self) -> Result<BoundedReader> {
+        Ok(BoundedReader::new(self.file, self.offset, self.length))
+    }
+}
+
+/// A `Read + Seek` view over `length` bytes of `file` starting at `start`
+pub(crate) struct BoundedReader {
+    file: File,
+    start: u64,
+    length: u64,
+    pos: u64,
+}
+
+impl BoundedReader {
+    pub(crate) fn new(file: File, start: u64, length: u64) -> Self {
+        Self {
+            file,
+            start,
+            length,
+            pos: 0,
+        }
+    }
+}
+
+impl Read for BoundedReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.length.saturating_sub(self.pos);
+        if remaining == 0 {
+            return Ok(0);
+        }
+        let max = remaining.min(buf.len() as u64) as usize;
+        self.file.seek(SeekFrom::Start(self.start + self.pos))?;
+        let n = self.file.read(&mut buf[..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Seek for BoundedReader {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(p) => p as i64,
+            SeekFrom::Current(p) => self.pos as i64 + p,
+            SeekFrom::End(p) => self.length as i64 + p,
+        };
+        if new_pos < 0 {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "seek to a negative position"));
+        }
+        self.pos = new_pos as u64;
+        Ok(self.pos)
+    }
+}
+
+/// Writes a `.bin` archive one chunk at a time
+pub struct ArchiveWriter {
+    file: File,
+    entries: Vec<(u128, u64, u64)>,
+}
+
+impl ArchiveWriter {
+    pub fn create(path: impl AsRef<std::path::Path>) -> Result<Self> {
+        Ok(Self {
+            file: File::create(path)?,
+            entries: Vec::new(),
+        })
+    }
+
+    /// Append a chunk to the archive
+    pub fn write_chunk(&mut self, id: u128, data: &[u8]) -> Result<()> {
+        self.file.write_all(&id.to_le_bytes())?;
+        self.file.write_all(&(data.len() as u64).to_le_bytes())?;
+        let offset = self.file.stream_position()?;
+        self.file.write_all(data)?;
+        self.entries.push((id, offset, data.len() as u64));
+        Ok(())
+    }
+
+    /// Finish the archive, appending a binary-search-tree index footer over the chunks written
+    /// so far so later reads can find a chunk in O(log n) instead of scanning every header
+    pub fn finish(mut self) -> Result<()> {
+        self.entries.sort_by_key(|(id, ..)| *id);
+
+        let mut tree = vec![(0u128, 0u64, 0u64); self.entries.len()];
+        fill_eytzinger(&self.entries, &mut tree, 0, &mut 0);
+
+        let table_offset = self.file.stream_position()?;
+        for (id, offset, length) in &tree {
+            self.file.write_all(&id.to_le_bytes())?;
+            self.file.write_all(&offset.to_le_bytes())?;
+            self.file.write_all(&length.to_le_bytes())?;
+        }
+        self.file.write_all(&table_offset.to_le_bytes())?;
+        self.file.write_all(&(tree.len() as u64).to_le_bytes())?;
+        self.file.write_all(&FOOTER_MAGIC.to_le_bytes())?;
+        Ok(())
+    }
+}
+
+/// Place `sorted` entries into `tree` using the implicit BST ("Eytzinger") array layout: a
+/// search starts at index 0 and descends to the left child `2*i+1` or right child `2*i+2` on
+/// each comparison, terminating once the index reaches `tree.len()`. An in-order walk of that
+/// shape visits indices in the same order as `sorted`, so filling it is a plain in-order
+/// recursion that hands out entries from `sorted` as it goes.
+fn fill_eytzinger(sorted: &[(u128, u64, u64)], tree: &mut [(u128, u64, u64)], i: usize, next: &mut usize) {
+    if i >= tree.len() {
+        return;
+    }
+    fill_eytzinger(sorted, tree, 2 * i + 1, next);
+    tree[i] = sorted[*next];
+    *next += 1;
+    fill_eytzinger(sorted, tree, 2 * i + 2, next);
+}
+
+/// Binary search over a tree produced by [`fill_eytzinger`]
+fn search(tree: &[(u128, u64, u64)], id: u128) -> Option<(u64, u64)> {
+    let mut i = 0;
+    while i < tree.len() {
+        let (entry_id, offset, length) = tree[i];
+        if id == entry_id {
+            return Some((offset, length));
+        }
+        i = if id < entry_id { 2 * i + 1 } else { 2 * i + 2 };
+    }
+    None
+}
+
+/// Outcome of probing an archive's index footer for a chunk id
+pub(crate) enum ChunkLookup {
+    /// The footer has an entry for the id at this `(offset, length)`
+    Found(u64, u64),
+    /// A footer exists and was read, but it has no entry for the id: the chunk doesn't exist, and
+    /// the caller should *not* fall back to a linear scan (it would just walk off the last real
+    /// chunk into the footer table itself)
+    Missing,
+    /// The archive has no footer (or the trailer's magic doesn't match), so the caller should fall
+    /// back to a linear scan instead of treating it as an error
+    NoFooter,
+}
+
+/// Look up a chunk's `(offset, length)` via an archive's index footer
+pub(crate) fn lookup_chunk(file: &mut File, id: u128) -> Result<ChunkLookup> {
+    let len = file.seek(SeekFrom::End(0))?;
+    if len < TRAILER_LEN as u64 {
+        return Ok(ChunkLookup::NoFooter);
+    }
+
+    file.seek(SeekFrom::End(-(TRAILER_LEN as i64)))?;
+    let mut trailer = [0u8; TRAILER_LEN];
+    if file.read_exact(&mut trailer).is_err() {
+        return Ok(ChunkLookup::NoFooter);
+    }
+    let table_offset = u64::from_le_bytes(trailer[0..8].try_into().unwrap());
+    let entry_count = u64::from_le_bytes(trailer[8..16].try_into().unwrap()) as usize;
+    let magic = u32::from_le_bytes(trailer[16..20].try_into().unwrap());
+    if magic != FOOTER_MAGIC {
+        return Ok(ChunkLookup::NoFooter);
+    }
+
+    file.seek(SeekFrom::Start(table_offset))?;
+    let mut tree = Vec::with_capacity(entry_count);
+    for _ in 0..entry_count {
+        let mut entry = [0u8; ENTRY_LEN];
+        file.read_exact(&mut entry)?;
+        let entry_id = u128::from_le_bytes(entry[0..16].try_into().unwrap());
+        let offset = u64::from_le_bytes(entry[16..24].try_into().unwrap());
+        let length = u64::from_le_bytes(entry[24..32].try_into().unwrap());
+        tree.push((entry_id, offset, length));
+    }
+
+    Ok(match search(&tree, id) {
+        Some((offset, length)) => ChunkLookup::Found(offset, length),
+        None => ChunkLookup::Missing,
+    })
+}
@@ -7,6 +7,7 @@ pub use image::ImageFormat;
 use reerror::Result;
 
 /// Wrapper around using the `image` crate to parse images
+#[derive(Clone, Copy)]
 pub struct Img(pub ImageFormat);
 
 impl Format for Img {
@@ -0,0 +1,524 @@
+//! glTF 2.0 scene loading, covering both `.gltf` (with external buffers) and self-contained `.glb`
+
+use std::{collections::HashMap, io::Read};
+
+use mint::{Point2, Point3};
+use reerror::{conversions::invalid_argument, throw, Result, StatusCode};
+use serde::Deserialize;
+
+use crate::{
+    formats::{
+        img::{Img, ImageFormat},
+        Format,
+    },
+    Path,
+};
+
+use super::{Material, Mesh, Node, Scene};
+
+/// File format definition for glTF 2.0 scenes
+#[derive(Clone, Copy)]
+pub struct GltfScene;
+
+impl Format for GltfScene {
+    type Output = Scene<f32>;
+
+    fn parse(&self, path: &Path) -> Result<Self::Output> {
+        let mut bytes = Vec::new();
+        throw!(path.reader()?.read_to_end(&mut bytes), "reading glTF asset");
+
+        let (doc, glb_bin) = if bytes.starts_with(b"glTF") {
+            throw!(parse_glb(&bytes), "parsing .glb container")
+        } else {
+            (
+                throw!(serde_json::from_slice(&bytes), "parsing glTF JSON document"),
+                None,
+            )
+        };
+
+        let buffers = throw!(load_buffers(path, &doc, glb_bin), "loading glTF buffers");
+        let accessors = Accessors {
+            doc: &doc,
+            buffers: &buffers,
+        };
+
+        let materials = throw!(load_materials(path, &doc), "loading glTF materials");
+
+        let mesh_primitives: Vec<Vec<Mesh<f32>>> = doc
+            .meshes
+            .iter()
+            .map(|m| build_primitives(m, &accessors))
+            .collect::<Result<_>>()?;
+
+        let mut scene = Scene {
+            materials,
+            ..Default::default()
+        };
+
+        let scene_idx = doc.scene.unwrap_or(0);
+        if let Some(root) = doc.scenes.get(scene_idx) {
+            for &node_idx in &root.nodes {
+                scene.nodes.push(throw!(
+                    build_node(node_idx, &doc, &mesh_primitives),
+                    "building node {node_idx}"
+                ));
+            }
+        }
+
+        Ok(scene)
+    }
+}
+
+const GLB_MAGIC: u32 = 0x46546c67; // "glTF"
+const CHUNK_JSON: u32 = 0x4e4f534a;
+const CHUNK_BIN: u32 = 0x004e4942;
+
+fn parse_glb(bytes: &[u8]) -> Result<(Document, Option<Vec<u8>>)> {
+    if bytes.len() < 12 {
+        return Err(invalid_argument("glb file is smaller than its 12 byte header"));
+    }
+    let magic = u32::from_le_bytes(bytes[0..4].try_into().unwrap());
+    if magic != GLB_MAGIC {
+        return Err(invalid_argument("glb file has an invalid magic number"));
+    }
+    let total_len = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+
+    let mut offset = 12;
+    let mut json = None;
+    let mut bin = None;
+    while offset + 8 <= total_len.min(bytes.len()) {
+        let chunk_len = u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize;
+        let chunk_type = u32::from_le_bytes(bytes[offset + 4..offset + 8].try_into().unwrap());
+        let data_start = offset + 8;
+        let data_end = data_start + chunk_len;
+        let data = throw!(
+            bytes.get(data_start..data_end),
+            if none StatusCode::OutOfRange,
+            "glb chunk at offset {offset} overruns the file"
+        );
+        match chunk_type {
+            CHUNK_JSON => json = Some(throw!(serde_json::from_slice(data), "parsing JSON chunk")),
+            CHUNK_BIN => bin = Some(data.to_vec()),
+            _ => { /* unknown chunk types must be ignored per the glb spec */ }
+        }
+        offset = data_end;
+    }
+
+    let json = throw!(json, if none StatusCode::OutOfRange, "glb file has no JSON chunk");
+    Ok((json, bin))
+}
+
+fn load_buffers(path: &Path, doc: &Document, glb_bin: Option<Vec<u8>>) -> Result<Vec<Vec<u8>>> {
+    let mut out = Vec::with_capacity(doc.buffers.len());
+    for (i, buffer) in doc.buffers.iter().enumerate() {
+        let bytes = match &buffer.uri {
+            Some(uri) => {
+                let sibling = throw!(path.sibling(uri), "resolving buffer {i} uri '{uri}'");
+                let loaded = throw!(
+                    crate::load(sibling.to_string(), crate::formats::misc::Bin),
+                    "loading buffer {i}"
+                );
+                (*loaded).clone()
+            }
+            None => throw!(
+                glb_bin.clone(),
+                if none StatusCode::OutOfRange,
+                "buffer {i} has no uri and the glb has no BIN chunk"
+            ),
+        };
+        out.push(bytes);
+    }
+    Ok(out)
+}
+
+fn load_materials(path: &Path, doc: &Document) -> Result<Vec<Material>> {
+    doc.materials
+        .iter()
+        .map(|m| build_material(path, doc, m))
+        .collect()
+}
+
+fn build_material(path: &Path, doc: &Document, def: &MaterialDef) -> Result<Material> {
+    let mut material = Material {
+        name: def.name.clone().unwrap_or_default(),
+        ..Default::default()
+    };
+
+    if let Some(pbr) = &def.pbr_metallic_roughness {
+        if let Some([r, g, b, _a]) = pbr.base_color_factor {
+            material.diffuse = [r, g, b];
+        }
+        if let Some(tex) = &pbr.base_color_texture {
+            material.diffuse_map = Some(throw!(
+                load_texture(path, doc, tex.index),
+                "loading baseColorTexture"
+            ));
+        }
+    }
+    if let Some(tex) = &def.normal_texture {
+        material.bump_map = Some(throw!(load_texture(path, doc, tex.index), "loading normalTexture"));
+    }
+
+    Ok(material)
+}
+
+/// Resolve a texture index to its backing image's `Path`, loading it through `Img` so it lands
+/// in the shared asset cache alongside the scene's buffers
+fn load_texture(path: &Path, doc: &Document, texture_idx: usize) -> Result<Path> {
+    let texture = throw!(
+        doc.textures.get(texture_idx),
+        if none StatusCode::OutOfRange,
+        "texture index {texture_idx} out of range"
+    );
+    let image = throw!(
+        doc.images.get(texture.source),
+        if none StatusCode::OutOfRange,
+        "image index {} out of range",
+        texture.source
+    );
+    let uri = throw!(
+        image.uri.as_deref(),
+        if none StatusCode::OutOfRange,
+        "image {} has no uri (buffer-embedded glTF images are not supported)",
+        texture.source
+    );
+    let sibling = throw!(path.sibling(uri), "resolving texture uri '{uri}'");
+
+    let ext = uri.rsplit('.').next().unwrap_or_default();
+    let format = throw!(
+        ImageFormat::from_extension(ext),
+        if none StatusCode::OutOfRange,
+        "cannot guess an image format from uri '{uri}'"
+    );
+    throw!(crate::load(sibling.to_string(), Img(format)), "loading texture '{uri}'");
+
+    Ok(sibling)
+}
+
+fn build_primitives(def: &MeshDef, accessors: &Accessors) -> Result<Vec<Mesh<f32>>> {
+    let mut out = Vec::with_capacity(def.primitives.len());
+    for prim in &def.primitives {
+        let mut mesh = Mesh {
+            material: prim.material,
+            ..Default::default()
+        };
+
+        if let Some(&accessor) = prim.attributes.get("POSITION") {
+            let flat = throw!(accessors.read_f32(accessor, 3), "reading POSITION attribute");
+            mesh.verts = flat
+                .chunks_exact(3)
+                .map(|c| Point3 { x: c[0], y: c[1], z: c[2] })
+                .collect();
+        }
+        if let Some(&accessor) = prim.attributes.get("NORMAL") {
+            let flat = throw!(accessors.read_f32(accessor, 3), "reading NORMAL attribute");
+            mesh.normals = flat
+                .chunks_exact(3)
+                .map(|c| Point3 { x: c[0], y: c[1], z: c[2] })
+                .collect();
+        }
+        if let Some(&accessor) = prim.attributes.get("TANGENT") {
+            let flat = throw!(accessors.read_f32(accessor, 4), "reading TANGENT attribute");
+            // glTF tangents are vec4 (xyz + w handedness); the w sign only matters for
+            // deriving the bitangent at render time, so it isn't kept alongside xyz here.
+            mesh.tangents = flat
+                .chunks_exact(4)
+                .map(|c| Point3 { x: c[0], y: c[1], z: c[2] })
+                .collect();
+        }
+        if let Some(&accessor) = prim.attributes.get("TEXCOORD_0") {
+            let flat = throw!(accessors.read_f32(accessor, 2), "reading TEXCOORD_0 attribute");
+            mesh.uvs = flat
+                .chunks_exact(2)
+                .map(|c| Point2 { x: c[0], y: c[1] })
+                .collect();
+        }
+        if let Some(accessor) = prim.indices {
+            mesh.indices = throw!(accessors.read_indices(accessor), "reading primitive indices");
+        }
+
+        out.push(mesh);
+    }
+    Ok(out)
+}
+
+fn build_node(idx: usize, doc: &Document, mesh_primitives: &[Vec<Mesh<f32>>]) -> Result<Node<f32>> {
+    let def = throw!(doc.nodes.get(idx), if none StatusCode::OutOfRange, "node index {idx} out of range");
+
+    let transform = match def.matrix {
+        Some(m) => column_major_to_rows(m),
+        None => trs_to_matrix(
+            def.translation.unwrap_or([0.0, 0.0, 0.0]),
+            def.rotation.unwrap_or([0.0, 0.0, 0.0, 1.0]),
+            def.scale.unwrap_or([1.0, 1.0, 1.0]),
+        ),
+    };
+
+    let mut node = Node {
+        name: def.name.clone().unwrap_or_else(|| format!("node{idx}")),
+        transform,
+        meshes: Vec::new(),
+        children: Vec::new(),
+    };
+
+    if let Some(mesh_idx) = def.mesh {
+        let prims = throw!(
+            mesh_primitives.get(mesh_idx),
+            if none StatusCode::OutOfRange,
+            "mesh index {mesh_idx} out of range"
+        );
+        node.meshes.extend(prims.iter().cloned());
+    }
+
+    for &child_idx in &def.children {
+        node.children
+            .push(throw!(build_node(child_idx, doc, mesh_primitives), "building node {child_idx}"));
+    }
+
+    Ok(node)
+}
+
+fn column_major_to_rows(m: [f32; 16]) -> [[f32; 4]; 4] {
+    let mut out = [[0.0; 4]; 4];
+    for col in 0..4 {
+        for row in 0..4 {
+            out[row][col] = m[col * 4 + row];
+        }
+    }
+    out
+}
+
+fn trs_to_matrix(t: [f32; 3], r: [f32; 4], s: [f32; 3]) -> [[f32; 4]; 4] {
+    let [x, y, z, w] = r;
+    let (xx, yy, zz) = (x * x, y * y, z * z);
+    let (xy, xz, yz) = (x * y, x * z, y * z);
+    let (wx, wy, wz) = (w * x, w * y, w * z);
+
+    let rot = [
+        [1.0 - 2.0 * (yy + zz), 2.0 * (xy - wz), 2.0 * (xz + wy)],
+        [2.0 * (xy + wz), 1.0 - 2.0 * (xx + zz), 2.0 * (yz - wx)],
+        [2.0 * (xz - wy), 2.0 * (yz + wx), 1.0 - 2.0 * (xx + yy)],
+    ];
+
+    let mut out = super::IDENTITY4;
+    for (row, rot_row) in rot.iter().enumerate() {
+        for col in 0..3 {
+            out[row][col] = rot_row[col] * s[col];
+        }
+        out[row][3] = t[row];
+    }
+    out
+}
+
+struct Accessors<'a> {
+    doc: &'a Document,
+    buffers: &'a [Vec<u8>],
+}
+
+impl Accessors<'_> {
+    fn locate(&self, accessor_idx: usize) -> Result<(&AccessorDef, &BufferViewDef, &Vec<u8>)> {
+        let accessor = throw!(
+            self.doc.accessors.get(accessor_idx),
+            if none StatusCode::OutOfRange,
+            "accessor index {accessor_idx} out of range"
+        );
+        let view_idx = throw!(
+            accessor.buffer_view,
+            if none StatusCode::OutOfRange,
+            "accessor {accessor_idx} has no bufferView (sparse accessors are not supported)"
+        );
+        let view = throw!(
+            self.doc.buffer_views.get(view_idx),
+            if none StatusCode::OutOfRange,
+            "bufferView index {view_idx} out of range"
+        );
+        let buffer = throw!(
+            self.buffers.get(view.buffer),
+            if none StatusCode::OutOfRange,
+            "buffer index {} out of range",
+            view.buffer
+        );
+        Ok((accessor, view, buffer))
+    }
+
+    /// Read an accessor of `componentType` 5126 (f32) as a flat, interleaved-free list of floats
+    fn read_f32(&self, accessor_idx: usize, components: usize) -> Result<Vec<f32>> {
+        let (accessor, view, buffer) = self.locate(accessor_idx)?;
+        if accessor.component_type != 5126 {
+            return Err(invalid_argument(format!(
+                "expected an f32 accessor (componentType 5126), found {}",
+                accessor.component_type
+            )));
+        }
+
+        let stride = view.byte_stride.unwrap_or(components * 4);
+        let base = view.byte_offset + accessor.byte_offset;
+
+        let mut out = Vec::with_capacity(accessor.count * components);
+        for i in 0..accessor.count {
+            let start = base + i * stride;
+            for c in 0..components {
+                let off = start + c * 4;
+                let bytes = throw!(
+                    buffer.get(off..off + 4),
+                    if none StatusCode::OutOfRange,
+                    "accessor {accessor_idx} reads past the end of its buffer"
+                );
+                out.push(f32::from_le_bytes(bytes.try_into().unwrap()));
+            }
+        }
+        Ok(out)
+    }
+
+    /// Read an index accessor (`componentType` 5121/5123/5125) widened to `u32`
+    fn read_indices(&self, accessor_idx: usize) -> Result<Vec<u32>> {
+        let (accessor, view, buffer) = self.locate(accessor_idx)?;
+        let size = match accessor.component_type {
+            5121 => 1, // u8
+            5123 => 2, // u16
+            5125 => 4, // u32
+            other => return Err(invalid_argument(format!("unsupported index componentType {other}"))),
+        };
+        let stride = view.byte_stride.unwrap_or(size);
+        let base = view.byte_offset + accessor.byte_offset;
+
+        let mut out = Vec::with_capacity(accessor.count);
+        for i in 0..accessor.count {
+            let start = base + i * stride;
+            let bytes = throw!(
+                buffer.get(start..start + size),
+                if none StatusCode::OutOfRange,
+                "accessor {accessor_idx} reads past the end of its buffer"
+            );
+            out.push(match size {
+                1 => bytes[0] as u32,
+                2 => u16::from_le_bytes(bytes.try_into().unwrap()) as u32,
+                4 => u32::from_le_bytes(bytes.try_into().unwrap()),
+                _ => unreachable!(),
+            });
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct Document {
+    #[serde(default)]
+    scene: Option<usize>,
+    #[serde(default)]
+    scenes: Vec<GltfSceneDef>,
+    #[serde(default)]
+    nodes: Vec<NodeDef>,
+    #[serde(default)]
+    meshes: Vec<MeshDef>,
+    #[serde(default)]
+    materials: Vec<MaterialDef>,
+    #[serde(default)]
+    accessors: Vec<AccessorDef>,
+    #[serde(default, rename = "bufferViews")]
+    buffer_views: Vec<BufferViewDef>,
+    #[serde(default)]
+    buffers: Vec<BufferDef>,
+    #[serde(default)]
+    textures: Vec<TextureDef>,
+    #[serde(default)]
+    images: Vec<ImageDef>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct GltfSceneDef {
+    #[serde(default)]
+    nodes: Vec<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct NodeDef {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    children: Vec<usize>,
+    #[serde(default)]
+    mesh: Option<usize>,
+    #[serde(default)]
+    matrix: Option<[f32; 16]>,
+    #[serde(default)]
+    translation: Option<[f32; 3]>,
+    #[serde(default)]
+    rotation: Option<[f32; 4]>,
+    #[serde(default)]
+    scale: Option<[f32; 3]>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MeshDef {
+    primitives: Vec<PrimitiveDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PrimitiveDef {
+    attributes: HashMap<String, usize>,
+    #[serde(default)]
+    indices: Option<usize>,
+    #[serde(default)]
+    material: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct MaterialDef {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default, rename = "pbrMetallicRoughness")]
+    pbr_metallic_roughness: Option<PbrMetallicRoughnessDef>,
+    #[serde(default, rename = "normalTexture")]
+    normal_texture: Option<TextureRefDef>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct PbrMetallicRoughnessDef {
+    #[serde(default, rename = "baseColorFactor")]
+    base_color_factor: Option<[f32; 4]>,
+    #[serde(default, rename = "baseColorTexture")]
+    base_color_texture: Option<TextureRefDef>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextureRefDef {
+    index: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct TextureDef {
+    source: usize,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct ImageDef {
+    #[serde(default)]
+    uri: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AccessorDef {
+    #[serde(default, rename = "bufferView")]
+    buffer_view: Option<usize>,
+    #[serde(default, rename = "byteOffset")]
+    byte_offset: usize,
+    #[serde(rename = "componentType")]
+    component_type: u32,
+    count: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct BufferViewDef {
+    buffer: usize,
+    #[serde(default, rename = "byteOffset")]
+    byte_offset: usize,
+    #[serde(default, rename = "byteStride")]
+    byte_stride: Option<usize>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct BufferDef {
+    #[serde(default)]
+    uri: Option<String>,
+}
@@ -0,0 +1,98 @@
+//! Mesh and scene types shared by the mesh format parsers
+
+use mint::{Point2, Point3};
+
+use crate::Path;
+
+pub mod gltf;
+pub mod mtl;
+pub mod obj;
+
+pub use gltf::GltfScene;
+pub use mtl::Mtl;
+pub use obj::{ObjMesh, ObjScene};
+
+/// Identity 4x4 matrix, stored row-major (`transform[row][col]`)
+pub const IDENTITY4: [[f32; 4]; 4] = [
+    [1.0, 0.0, 0.0, 0.0],
+    [0.0, 1.0, 0.0, 0.0],
+    [0.0, 0.0, 1.0, 0.0],
+    [0.0, 0.0, 0.0, 1.0],
+];
+
+/// A single mesh primitive's vertex attributes
+///
+/// Attribute vectors are indexed in parallel: vertex `i`'s position is `verts[i]`, its normal
+/// (if present) is `normals[i]`, and so on. `indices`, when non-empty, draws the mesh as an
+/// indexed primitive instead of a flat vertex stream.
+#[derive(Debug, Clone, Default)]
+pub struct Mesh<T> {
+    pub verts: Vec<Point3<T>>,
+    pub normals: Vec<Point3<T>>,
+    pub tangents: Vec<Point3<T>>,
+    pub uvs: Vec<Point2<T>>,
+    pub indices: Vec<u32>,
+    /// Index into the owning `Scene`'s `materials`
+    pub material: Option<usize>,
+}
+
+/// A node in a scene's hierarchy
+///
+/// Carries its own transform plus zero or more mesh primitives, and may have children whose
+/// transforms are relative to this node.
+#[derive(Debug, Clone)]
+pub struct Node<T> {
+    pub name: String,
+    pub transform: [[f32; 4]; 4],
+    pub meshes: Vec<Mesh<T>>,
+    pub children: Vec<Node<T>>,
+}
+
+impl Node<f32> {
+    /// A named node at the origin with no meshes or children
+    pub fn identity(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            transform: IDENTITY4,
+            meshes: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+}
+
+/// A loaded scene: a forest of nodes plus the materials its meshes reference
+#[derive(Debug, Clone, Default)]
+pub struct Scene<T> {
+    pub nodes: Vec<Node<T>>,
+    pub materials: Vec<Material>,
+}
+
+/// A surface material, in the style of a Wavefront `.mtl` entry
+#[derive(Debug, Clone)]
+pub struct Material {
+    pub name: String,
+    pub ambient: [f32; 3],
+    pub diffuse: [f32; 3],
+    pub specular: [f32; 3],
+    pub shininess: f32,
+    pub opacity: f32,
+    pub diffuse_map: Option<Path>,
+    pub bump_map: Option<Path>,
+    pub specular_map: Option<Path>,
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            ambient: [0.0; 3],
+            diffuse: [0.0; 3],
+            specular: [0.0; 3],
+            shininess: 0.0,
+            opacity: 1.0,
+            diffuse_map: None,
+            bump_map: None,
+            specular_map: None,
+        }
+    }
+}
@@ -0,0 +1,107 @@
+use std::io::{BufRead, BufReader};
+
+use log::error;
+use reerror::{throw, Result};
+
+use crate::{formats::Format, Path};
+
+use super::Material;
+
+/// File format definition for Wavefront `.mtl` material libraries
+pub struct Mtl;
+
+impl Format for Mtl {
+    type Output = Vec<Material>;
+
+    fn parse(&self, path: &Path) -> Result<Self::Output> {
+        let reader = BufReader::new(path.reader()?);
+
+        let mut materials = Vec::new();
+        let mut cur: Option<Material> = None;
+
+        for (n, line) in reader.lines().enumerate() {
+            let n = n + 1; // files usually aren't 0 indexed
+            let line = throw!(line, "Failed to parse line {n}");
+
+            let tokens: Vec<&str> = line.split_whitespace().collect();
+            match tokens[..] {
+                [] => { /* blank line, common between newmtl blocks in Blender/Maya exports */ }
+                [first, ..] if first.starts_with('#') => { /* comment */ }
+                ["newmtl", name] => {
+                    if let Some(mat) = cur.take() {
+                        materials.push(mat);
+                    }
+                    cur = Some(Material {
+                        name: name.to_string(),
+                        ..Default::default()
+                    });
+                }
+                ["Ka", r, g, b] => {
+                    if let Some(mat) = &mut cur {
+                        mat.ambient = [
+                            throw!(r.parse(), "parsing Ka red on line {n}: '{r}'"),
+                            throw!(g.parse(), "parsing Ka green on line {n}: '{g}'"),
+                            throw!(b.parse(), "parsing Ka blue on line {n}: '{b}'"),
+                        ];
+                    }
+                }
+                ["Kd", r, g, b] => {
+                    if let Some(mat) = &mut cur {
+                        mat.diffuse = [
+                            throw!(r.parse(), "parsing Kd red on line {n}: '{r}'"),
+                            throw!(g.parse(), "parsing Kd green on line {n}: '{g}'"),
+                            throw!(b.parse(), "parsing Kd blue on line {n}: '{b}'"),
+                        ];
+                    }
+                }
+                ["Ks", r, g, b] => {
+                    if let Some(mat) = &mut cur {
+                        mat.specular = [
+                            throw!(r.parse(), "parsing Ks red on line {n}: '{r}'"),
+                            throw!(g.parse(), "parsing Ks green on line {n}: '{g}'"),
+                            throw!(b.parse(), "parsing Ks blue on line {n}: '{b}'"),
+                        ];
+                    }
+                }
+                ["Ns", ns] => {
+                    if let Some(mat) = &mut cur {
+                        mat.shininess = throw!(ns.parse(), "parsing Ns on line {n}: '{ns}'");
+                    }
+                }
+                ["d", d] => {
+                    if let Some(mat) = &mut cur {
+                        mat.opacity = throw!(d.parse(), "parsing d on line {n}: '{d}'");
+                    }
+                }
+                ["Tr", tr] => {
+                    if let Some(mat) = &mut cur {
+                        let tr: f32 = throw!(tr.parse(), "parsing Tr on line {n}: '{tr}'");
+                        mat.opacity = 1.0 - tr;
+                    }
+                }
+                ["map_Kd", file] => {
+                    if let Some(mat) = &mut cur {
+                        mat.diffuse_map = Some(throw!(path.sibling(file), "resolving map_Kd on line {n}: '{file}'"));
+                    }
+                }
+                ["map_Bump", file] | ["bump", file] => {
+                    if let Some(mat) = &mut cur {
+                        mat.bump_map = Some(throw!(path.sibling(file), "resolving map_Bump on line {n}: '{file}'"));
+                    }
+                }
+                ["map_Ks", file] => {
+                    if let Some(mat) = &mut cur {
+                        mat.specular_map = Some(throw!(path.sibling(file), "resolving map_Ks on line {n}: '{file}'"));
+                    }
+                }
+                _ => error!("Unrecognized .mtl command '{line}'"),
+            }
+        }
+
+        if let Some(mat) = cur.take() {
+            materials.push(mat);
+        }
+
+        Ok(materials)
+    }
+}
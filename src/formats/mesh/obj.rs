@@ -6,7 +6,7 @@ use reerror::{conversions::invalid_argument, throw, Result, StatusCode};
 
 use crate::{formats::Format, Path};
 
-use super::{Mesh, Scene};
+use super::{Material, Mesh, Mtl, Node, Scene};
 
 /// File format definition for Wavefront obj files
 #[derive(Clone, Copy)]
@@ -17,11 +17,25 @@ impl Format for ObjMesh {
 
     fn parse(&self, path: &Path) -> Result<Self::Output> {
         let mut scene = (ObjScene).parse(path)?;
-        Ok(scene.nodes.pop().unwrap().0)
+        let mut node = throw!(
+            scene.nodes.pop(),
+            if none StatusCode::NotFound,
+            "obj file '{path}' has no objects/groups with any faces"
+        );
+        let mut mesh = throw!(
+            node.meshes.pop(),
+            if none StatusCode::NotFound,
+            "obj file '{path}' has no faces"
+        );
+        // `material` indexes into the Scene's materials vec, which we just dropped along with
+        // the rest of the scene; there's nowhere left for that index to point
+        mesh.material = None;
+        Ok(mesh)
     }
 }
 
 /// File format definition for Wavefront obj files
+#[derive(Clone, Copy)]
 pub struct ObjScene;
 
 impl Format for ObjScene {
@@ -33,11 +47,16 @@ impl Format for ObjScene {
         let mut normals: Vec<Point3<f32>> = vec![];
         let mut uvs: Vec<Point2<f32>> = vec![];
         let mut indices: Vec<(usize, Option<usize>, Option<usize>)> = vec![];
-        let mut scene: Vec<(Mesh<f32>, String)> = vec![];
-        let mut cur_obj: Option<String> = None;
+
+        let mut materials: Vec<Material> = vec![];
+        let mut cur_material: Option<usize> = None;
+
+        let mut scene: Vec<Node<f32>> = vec![];
+        let mut last_n = 0;
 
         for (n, line) in reader.lines().enumerate() {
             let n = n + 1; // files usually aren't 0 indexed
+            last_n = n;
             let line = throw!(line, "Failed to parse line {n}");
 
             let tokens: Vec<&str> = line.split_whitespace().collect();
@@ -58,53 +77,46 @@ impl Format for ObjScene {
                     z: throw!(z.parse(), "parsing z coord of normal on line {n}: '{z}'"),
                 }),
                 ["o", name] => {
-                    if let Some(name) = cur_obj {
-                        // do some validation of the parsed data
-                        if indices.len() % 3 != 0 {
-                            warn!("object does not have a valid number of indices ({}), expected a multiple of 3", indices.len());
-                        }
-                        if verts.len() > normals.len() {
-                            warn!("found {} vertices and {} normals, some vertices will be missing normals", verts.len(), normals.len());
-                        }
-                        if verts.len() > uvs.len() {
-                            warn!("found {} vertices and {} uv coordinates, some vertices will be missing uv coords", verts.len(), uvs.len());
-                        }
-
-                        // build a mesh from parsed data
-                        let mut mesh = Mesh::default();
-                        for (v, uv, norm) in &indices {
-                            mesh.verts.push(*throw!(verts.get(*v - 1),
-                                if none StatusCode::OutOfRange,
-                                "on line {n} vertex index  '{v}' max value is {}",
-                                verts.len()
-                            ));
-                            if let Some(norm) = norm {
-                                mesh.normals.push(*throw!(normals.get(*norm - 1),
-                                    if none StatusCode::OutOfRange,
-                                    "on line {n} normal index '{norm}' max value is {}",
-                                    normals.len()
-                                ));
-                            }
-                            if let Some(uv) = uv {
-                                mesh.uvs.push(*throw!(uvs.get(*uv - 1),
-                                    if none StatusCode::OutOfRange,
-                                    "on line {n} uv index '{uv}' max value is {}",
-                                    uvs.len()
-                                ));
-                            }
-                        }
-
-                        // add mesh to scene
-                        scene.push((mesh, name.to_string()));
-
-                        // clear the current info
-                        indices.clear();
-                        verts.clear();
-                        normals.clear();
-                        uvs.clear();
+                    let mesh = throw!(
+                        flush_mesh(n, &mut indices, &verts, &normals, &uvs, cur_material),
+                        "finishing mesh before object '{name}' on line {n}"
+                    );
+                    if let Some(mesh) = mesh {
+                        push_mesh(&mut scene, mesh);
+                    }
+                    scene.push(Node::identity(name.to_string()));
+                }
+                ["g", ..] => {
+                    // a new group starts a new draw call but shares the file's vertex pool
+                    let mesh = throw!(
+                        flush_mesh(n, &mut indices, &verts, &normals, &uvs, cur_material),
+                        "finishing mesh before group on line {n}"
+                    );
+                    if let Some(mesh) = mesh {
+                        push_mesh(&mut scene, mesh);
+                    }
+                }
+                ["s", ..] => { /* smoothing groups aren't represented in this crate's flat mesh format */ }
+                ["mtllib", file] => {
+                    let mtl_path = throw!(path.sibling(file), "resolving mtllib '{file}' on line {n}");
+                    let loaded = throw!(
+                        crate::load(mtl_path.to_string(), Mtl),
+                        "loading mtllib '{file}' on line {n}"
+                    );
+                    materials.extend((*loaded).clone());
+                }
+                ["usemtl", name] => {
+                    let mesh = throw!(
+                        flush_mesh(n, &mut indices, &verts, &normals, &uvs, cur_material),
+                        "finishing mesh before usemtl '{name}' on line {n}"
+                    );
+                    if let Some(mesh) = mesh {
+                        push_mesh(&mut scene, mesh);
+                    }
+                    cur_material = materials.iter().position(|m| m.name == name);
+                    if cur_material.is_none() {
+                        warn!("material '{name}' referenced by usemtl on line {n} was not found in any mtllib");
                     }
-                    // record the name of the last object
-                    cur_obj = Some(name.to_string());
                 }
                 ["f", a, b, c] => {
                     let mut parse_index = |index: &str| {
@@ -153,27 +165,79 @@ impl Format for ObjScene {
             }
         }
 
-        // record the last mesh since it won't have an `o` tag
-        // build a mesh from parsed data
-        let mut mesh = Mesh::default();
-        for (v, uv, norm) in &indices {
-            mesh.verts.push(verts[*v - 1]);
-            if let Some(norm) = norm {
-                mesh.normals.push(normals[*norm - 1]);
-            }
-            if let Some(uv) = uv {
-                mesh.uvs.push(uvs[*uv - 1]);
-            }
+        // record the last mesh since it won't have an `o`/`g`/`usemtl` boundary after it
+        let mesh = throw!(
+            flush_mesh(last_n, &mut indices, &verts, &normals, &uvs, cur_material),
+            "finishing final mesh"
+        );
+        if let Some(mesh) = mesh {
+            push_mesh(&mut scene, mesh);
         }
 
-        // add mesh to scene
-        scene.push((mesh, cur_obj.unwrap_or_else(|| String::from("<anonymous>"))));
+        Ok(Scene {
+            nodes: scene,
+            materials,
+        })
+    }
+}
+
+/// Turn the in-progress face range into a mesh primitive, if any faces were accumulated
+///
+/// `o`/`g`/`usemtl` only split draw calls; they don't reset the file's shared vertex/normal/uv
+/// pools, which OBJ indexes globally across the whole document.
+fn flush_mesh(
+    n: usize,
+    indices: &mut Vec<(usize, Option<usize>, Option<usize>)>,
+    verts: &[Point3<f32>],
+    normals: &[Point3<f32>],
+    uvs: &[Point2<f32>],
+    material: Option<usize>,
+) -> Result<Option<Mesh<f32>>> {
+    if indices.is_empty() {
+        return Ok(None);
+    }
 
-        let mut out_scene = Scene::default();
-        for elem in scene {
-            out_scene.nodes.push(elem);
+    if indices.len() % 3 != 0 {
+        warn!(
+            "object does not have a valid number of indices ({}), expected a multiple of 3",
+            indices.len()
+        );
+    }
+
+    let mut mesh = Mesh {
+        material,
+        ..Default::default()
+    };
+    for (v, uv, norm) in indices.iter() {
+        mesh.verts.push(*throw!(verts.get(*v - 1),
+            if none StatusCode::OutOfRange,
+            "on line {n} vertex index '{v}' max value is {}",
+            verts.len()
+        ));
+        if let Some(norm) = norm {
+            mesh.normals.push(*throw!(normals.get(*norm - 1),
+                if none StatusCode::OutOfRange,
+                "on line {n} normal index '{norm}' max value is {}",
+                normals.len()
+            ));
+        }
+        if let Some(uv) = uv {
+            mesh.uvs.push(*throw!(uvs.get(*uv - 1),
+                if none StatusCode::OutOfRange,
+                "on line {n} uv index '{uv}' max value is {}",
+                uvs.len()
+            ));
         }
+    }
+    indices.clear();
+
+    Ok(Some(mesh))
+}
 
-        Ok(out_scene)
+/// Append `mesh` to the in-progress node, creating an anonymous one if no `o` has been seen yet
+fn push_mesh(scene: &mut Vec<Node<f32>>, mesh: Mesh<f32>) {
+    if scene.is_empty() {
+        scene.push(Node::identity("<anonymous>"));
     }
+    scene.last_mut().unwrap().meshes.push(mesh);
 }
@@ -2,6 +2,7 @@ use crate::{formats::Format, Path};
 use reerror::Result;
 
 /// File format defintion for a byte buffer
+#[derive(Clone, Copy)]
 pub struct Bin;
 
 impl Format for Bin {
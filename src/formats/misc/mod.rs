@@ -0,0 +1,7 @@
+//! Small generic file formats that don't deserve their own top-level module
+
+mod bin;
+mod txt;
+
+pub use bin::Bin;
+pub use txt::Txt;
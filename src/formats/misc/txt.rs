@@ -1,6 +1,7 @@
 use crate::{formats::Format, Path};
 use reerror::Result;
 /// File format defintion for a text file
+#[derive(Clone, Copy)]
 pub struct Txt;
 
 impl Format for Txt {
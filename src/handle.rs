@@ -0,0 +1,27 @@
+//! The typed handle returned by [`crate::load`]
+
+use std::{ops::Deref, rc::Rc, sync::Arc};
+
+/// A cheaply-clonable handle to a loaded asset
+///
+/// Wraps the cached `Arc<T>` behind a thread-local `Rc`, so cloning a `Handle` (to share one
+/// loaded mesh across systems on the same thread) is just an `Rc` bump, not an atomic one.
+pub struct Handle<T: ?Sized> {
+    pub(crate) inner: Rc<Arc<T>>,
+}
+
+impl<T: ?Sized> Clone for Handle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Rc::clone(&self.inner),
+        }
+    }
+}
+
+impl<T: ?Sized> Deref for Handle<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.inner.as_ref().as_ref()
+    }
+}
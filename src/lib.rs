@@ -19,12 +19,17 @@
 //! ```
 
 pub mod bin;
-//pub mod handle;
+mod handle;
 mod mgr;
 mod path;
+mod reload;
+mod scheme;
 pub use formats::Format;
+pub use handle::Handle;
 pub use mgr::*;
 pub use path::*;
+pub use reload::{on_reload, set_hot_reload};
+pub use scheme::{register_scheme, ReaderFactory};
 
 /// File formats implementations
 pub mod formats {
@@ -1,18 +1,23 @@
 use std::{
-    any::{type_name, Any, TypeId},
+    any::{type_name, Any},
     cell::RefCell,
     collections::{hash_map::DefaultHasher, HashMap},
     hash::{Hash, Hasher},
     mem,
     rc::{self, Rc},
-    sync::{self, Arc, RwLock},
+    sync::{
+        self,
+        atomic::{AtomicUsize, Ordering},
+        mpsc, Arc, Condvar, Mutex, RwLock,
+    },
+    thread,
 };
 
 use log::{info, trace};
 use once_cell::{sync::Lazy, unsync};
 use reerror::{conversions::invalid_argument, throw, Error, Result};
 
-use crate::{formats::Format, path::Path};
+use crate::{formats::Format, handle::Handle, path::Path};
 
 static ASSET_CACHE: Lazy<RwLock<HashMap<u64, sync::Weak<dyn Any + Sync + Send>>>> =
     Lazy::new(|| RwLock::new(HashMap::new()));
@@ -26,7 +31,7 @@ thread_local! {
 ///
 /// It will check a cache of previously loaded assets before loading the asset and if the asset
 /// has not been cached it will cache the asset
-pub fn load<F, P, E>(path: P, format: F) -> Result<Rc<Arc<F::Output>>>
+pub fn load<F, P, E>(path: P, format: F) -> Result<Handle<F::Output>>
 where
     F: Format + Any,
     F::Output: Any + Send + Sync,
@@ -43,6 +48,8 @@ where
     format.type_id().hash(&mut hash);
     let hash = hash.finish();
 
+    crate::reload::track(&path, hash);
+
     let asset = THREAD_ASSET_CACHE.with(|cache| -> Result<_> {
         // check thread-local cache
         let key = cache.borrow().get(&hash).map(rc::Weak::clone);
@@ -66,20 +73,19 @@ where
             }
         }
     })?;
-    // this is some cursed shit
-    // We need to manually implement downcast
-    // first check if the types are the same
-    if Arc::as_ref(&asset).type_id() != TypeId::of::<F::Output>() {
-        return Err(invalid_argument(format!(
-            "Expected didn't find asset of type: {}",
-            type_name::<F::Output>()
-        )));
-    }
 
-    // I'm 80% sure this is sound
-    let typed =
-        unsafe { mem::transmute::<Rc<Arc<dyn Any + Send + Sync>>, Rc<Arc<F::Output>>>(asset) };
-    Ok(typed)
+    let typed = match Arc::downcast::<F::Output>((*asset).clone()) {
+        Ok(typed) => typed,
+        Err(_) => {
+            return Err(invalid_argument(format!(
+                "Expected didn't find asset of type: {}",
+                type_name::<F::Output>()
+            )))
+        }
+    };
+    Ok(Handle {
+        inner: Rc::new(typed),
+    })
 }
 
 /// Attempt to load an asset from the global cache
@@ -109,6 +115,14 @@ where
     }
 }
 
+/// Remove `hash` from the global cache, forcing the next `load` of that path+format to reparse
+///
+/// Used by the hot-reload watcher. Doesn't affect assets already handed out via [`crate::Handle`]
+/// — only a future cache miss sees the fresh reparse.
+pub(crate) fn evict(hash: u64) {
+    ASSET_CACHE.write().unwrap().remove(&hash);
+}
+
 /// Add an asset to the global cache
 fn insert_cache<A: Any + Send + Sync>(hash: u64, asset: A) -> Result<Arc<dyn Any + Send + Sync>> {
     let asset: Arc<dyn Any + Send + Sync> = Arc::new(asset);
@@ -131,3 +145,173 @@ where
         std::any::type_name::<F>()
     ))
 }
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static WORKER_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+static WORK_QUEUE: Lazy<mpsc::Sender<Job>> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel::<Job>();
+    let rx = Arc::new(Mutex::new(rx));
+
+    let threads = match WORKER_THREADS.load(Ordering::Relaxed) {
+        0 => thread::available_parallelism().map(|n| n.get()).unwrap_or(4),
+        n => n,
+    };
+    for _ in 0..threads {
+        let rx = Arc::clone(&rx);
+        thread::spawn(move || loop {
+            let job = rx.lock().unwrap().recv();
+            match job {
+                Ok(job) => job(),
+                Err(_) => break,
+            }
+        });
+    }
+    tx
+});
+
+/// Configure how many worker threads back [`load_async`]
+///
+/// Must be called before the first `load_async`, as the pool is spawned lazily on first use and
+/// kept at a fixed size afterward. Defaults to [`std::thread::available_parallelism`].
+pub fn set_worker_threads(n: usize) {
+    WORKER_THREADS.store(n, Ordering::Relaxed);
+}
+
+fn submit(job: impl FnOnce() + Send + 'static) {
+    // the receiving end only goes away if a worker thread panicked mid-job; dropping the job is
+    // the best we can do in that case
+    let _ = WORK_QUEUE.send(Box::new(job));
+}
+
+/// Tracks a single in-flight `load_async` parse so concurrent requests for the same asset share
+/// one background job instead of racing to parse it twice
+struct Inflight {
+    // `Some(asset)` once the parse finished successfully; this keeps the asset alive between the
+    // background job finishing and the first caller materializing it through `load`, so it isn't
+    // evicted from `ASSET_CACHE` (which only ever holds weak references) before anyone can see it
+    state: Mutex<(bool, Option<Arc<dyn Any + Send + Sync>>)>,
+    done: Condvar,
+}
+
+static INFLIGHT: Lazy<Mutex<HashMap<u64, Arc<Inflight>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A handle to an asset being loaded on a background worker thread
+///
+/// Returned by [`load_async`]. Poll it with [`LoadHandle::poll`] or wait for it with
+/// [`LoadHandle::block`]; either way, the underlying asset lands in the same cache `load` uses,
+/// so other loads of the same path+format (sync or async) reuse it instead of re-parsing.
+pub struct LoadHandle<F> {
+    path: Path,
+    format: F,
+    inflight: Arc<Inflight>,
+}
+
+impl<F> LoadHandle<F>
+where
+    F: Format + Any + Clone,
+    F::Output: Any + Send + Sync,
+{
+    fn ready(path: Path, format: F) -> Self {
+        Self {
+            path,
+            format,
+            inflight: Arc::new(Inflight {
+                state: Mutex::new((true, None)),
+                done: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Check whether the asset has finished loading without blocking
+    pub fn is_ready(&self) -> bool {
+        self.inflight.state.lock().unwrap().0
+    }
+
+    /// Poll for the loaded asset, returning `None` if it isn't ready yet
+    pub fn poll(&self) -> Option<Result<Handle<F::Output>>> {
+        self.is_ready()
+            .then(|| load(self.path.clone(), self.format.clone()))
+    }
+
+    /// Block the calling thread until the asset has finished loading
+    pub fn block(self) -> Result<Handle<F::Output>> {
+        let mut state = self.inflight.state.lock().unwrap();
+        while !state.0 {
+            state = self.inflight.done.wait(state).unwrap();
+        }
+        drop(state);
+        load(self.path, self.format)
+    }
+}
+
+/// Enqueue `path`/`format` to be loaded on a background worker thread, returning a handle that
+/// can be polled or blocked on
+///
+/// Loading many meshes/textures up front no longer has to block the calling thread one at a
+/// time: kick them all off with `load_async` and keep doing other work while the worker pool
+/// loads and parses them in parallel. Once a handle reports ready, `load` of the same path and
+/// format (on any thread) returns the same asset instead of re-parsing it.
+pub fn load_async<F, P, E>(path: P, format: F) -> Result<LoadHandle<F>>
+where
+    F: Format + Any + Clone + Send + 'static,
+    F::Output: Any + Send + Sync,
+    P: TryInto<Path, Error = E>,
+    E: Into<Error>,
+{
+    let path = match path.try_into() {
+        Ok(path) => path,
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut hasher = DefaultHasher::new();
+    path.hash(&mut hasher);
+    format.type_id().hash(&mut hasher);
+    let hash = hasher.finish();
+
+    let already_cached = ASSET_CACHE
+        .read()
+        .unwrap()
+        .get(&hash)
+        .map(|a| a.upgrade().is_some())
+        .unwrap_or(false);
+    if already_cached {
+        return Ok(LoadHandle::ready(path, format));
+    }
+
+    let inflight = Arc::clone(
+        INFLIGHT
+            .lock()
+            .unwrap()
+            .entry(hash)
+            .or_insert_with(|| {
+                let inflight = Arc::new(Inflight {
+                    state: Mutex::new((false, None)),
+                    done: Condvar::new(),
+                });
+                let job_inflight = Arc::clone(&inflight);
+                let job_path = path.clone();
+                let job_format = format.clone();
+                submit(move || {
+                    let keep_alive = load_asset(job_path, job_format)
+                        .ok()
+                        .and_then(|asset| insert_cache(hash, asset).ok());
+
+                    let mut state = job_inflight.state.lock().unwrap();
+                    *state = (true, keep_alive);
+                    drop(state);
+                    job_inflight.done.notify_all();
+
+                    INFLIGHT.lock().unwrap().remove(&hash);
+                });
+                inflight
+            }),
+    );
+
+    Ok(LoadHandle {
+        path,
+        format,
+        inflight,
+    })
+}
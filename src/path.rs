@@ -17,7 +17,7 @@ use reerror::{Error, Result};
 /// Path used to identify an asset
 ///
 /// defaults to a file path if scheme is not specificed
-#[derive(Debug, Hash, PartialEq, Eq)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub enum Path {
     /// Filesystem path
     /// Loads the asset from disk using the provided filepath
@@ -27,6 +27,9 @@ pub enum Path {
     /// # Example
     /// `bin:path/to/file.bin#CHUNKID`
     Chunk(PathBuf, Option<u128>),
+    /// An asset served by a scheme registered with [`crate::register_scheme`], e.g. a remote
+    /// `http(s)://` asset. Holds the asset's full URI.
+    Remote(String),
 }
 
 impl TryFrom<&str> for Path {
@@ -49,6 +52,7 @@ impl TryFrom<&str> for Path {
                     .transpose()
                     .map_err(invalid_argument)),
             )),
+            scheme if crate::scheme::is_registered(scheme) => Ok(Path::Remote(path.to_string())),
             scheme => Err(unimplemented(format!("Unsupported URI scheme {}", scheme))),
         }
     }
@@ -85,6 +89,7 @@ impl Display for Path {
                 true => write!(f, "bin://{}", p.to_string_lossy()),
                 false => write!(f, "bin:{}", p.to_string_lossy()),
             },
+            Path::Remote(uri) => write!(f, "{uri}"),
         }
     }
 }
@@ -93,12 +98,48 @@ pub(crate) trait AssetReader: Read + Seek {}
 impl<T: Read + Seek> AssetReader for T {}
 
 impl Path {
+    /// Resolve `uri` relative to this asset, for things like a glTF document's external
+    /// buffer/image URIs or an `.obj`'s `mtllib`/texture references
+    pub(crate) fn sibling(&self, uri: &str) -> Result<Path> {
+        if uri.starts_with("data:") {
+            return Err(unimplemented("data URIs are not supported"));
+        }
+        let dir = |p: &PathBuf| p.parent().map(PathBuf::from).unwrap_or_default();
+        match self {
+            Path::File(p) => Ok(Path::File(dir(p).join(uri))),
+            Path::Chunk(p, _) => Ok(Path::Chunk(dir(p).join(uri), None)),
+            Path::Remote(_) => Err(unimplemented("resolving a sibling URI of a remote asset is not supported")),
+        }
+    }
+
+    /// The filesystem file backing this path, if any (used by the hot-reload watcher)
+    pub(crate) fn backing_file(&self) -> Option<&PathBuf> {
+        match self {
+            Path::File(p) | Path::Chunk(p, _) => Some(p),
+            Path::Remote(_) => None,
+        }
+    }
+
     pub(crate) fn reader(&self) -> Result<Box<dyn AssetReader>> {
         match self {
             Path::File(path) => Ok(Box::new(File::open(path)?)),
             Path::Chunk(path, Some(id)) => {
-                // find chunk in file
                 let mut file = File::open(path)?;
+
+                // an index footer lets us binary-search straight to the chunk
+                match throw!(crate::bin::lookup_chunk(&mut file, *id), "looking up chunk {id:X} in index") {
+                    crate::bin::ChunkLookup::Found(offset, length) => {
+                        return Ok(Box::new(crate::bin::BoundedReader::new(file, offset, length)));
+                    }
+                    // a footer exists and was consulted, so a linear scan would only walk off the
+                    // last real chunk into the footer table and misread it as chunk headers
+                    crate::bin::ChunkLookup::Missing => return Err(not_found(format!("Chunk not found: {id:X}"))),
+                    crate::bin::ChunkLookup::NoFooter => {}
+                }
+
+                // no footer: fall back to a linear scan. lookup_chunk leaves the cursor wherever
+                // its last seek landed (often EOF), so rewind first.
+                file.rewind()?;
                 while let Ok(chunk) = file.chunk() {
                     if chunk.id() == *id {
                         return Ok(Box::new(throw!(chunk.read(), "reading chunk")));
@@ -106,6 +147,12 @@ impl Path {
                 }
                 Err(not_found(format!("Chunk not found: {id:X}")))
             }
+            Path::Remote(uri) => {
+                let parsed = throw!(URIReference::try_from(uri.as_str()).map_err(invalid_argument));
+                let mut scheme = parsed.scheme().unwrap_or(&Scheme::File).clone();
+                scheme.normalize();
+                crate::scheme::open(scheme.as_str(), uri)
+            }
             _ => Err(unimplemented(
                 format!("Unsupported path type: {}", self).as_str(),
             )),
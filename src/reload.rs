@@ -0,0 +1,145 @@
+//! Opt-in hot-reload support
+//!
+//! The asset cache keys assets by `hash(path, format)` and keeps them around until every
+//! [`crate::Handle`] drops, so editing a model or texture on disk has no effect on its own.
+//! Enabling hot-reload with [`set_hot_reload`] makes `load` watch the filesystem file backing
+//! each newly loaded asset and, on change, evicts its cache entry so the next `load` of that
+//! path reparses it. A changed file may back several cache entries (e.g. multiple chunks of the
+//! same `.bin` archive), so a reverse index from filesystem path to cache hashes is kept
+//! alongside the watcher.
+//!
+//! Already-issued handles keep pointing at the old data; subscribe with [`on_reload`] to react to
+//! a reload (e.g. re-upload a mesh to the GPU) instead.
+//!
+//! The watcher watches each asset's *containing directory* rather than the file itself: editors
+//! commonly "save" by writing a temp file and renaming it over the original, which replaces the
+//! inode backing the file. A watch on the file itself would still be pinned to the old, now
+//! orphaned inode and would never see the new one; watching the directory tracks the name instead
+//! and keeps working across the rename.
+
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Mutex,
+    },
+    thread,
+};
+
+use log::warn;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use once_cell::sync::Lazy;
+
+use crate::path::Path;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+type ReloadCallback = Box<dyn Fn(u64) + Send + Sync>;
+
+struct Reload {
+    watcher: Mutex<RecommendedWatcher>,
+    /// canonicalized filesystem path -> the cache hashes it backs
+    index: Mutex<HashMap<PathBuf, Vec<u64>>>,
+    /// canonicalized directories already under watch, so each is only registered once
+    watched_dirs: Mutex<HashSet<PathBuf>>,
+    subscribers: Mutex<Vec<ReloadCallback>>,
+}
+
+static RELOAD: Lazy<Reload> = Lazy::new(|| {
+    let (tx, rx) = mpsc::channel();
+    let watcher =
+        notify::recommended_watcher(tx).expect("failed to start hot-reload file watcher");
+
+    thread::spawn(move || {
+        for res in rx {
+            match res {
+                Ok(event) => handle_event(event),
+                Err(e) => warn!("hot-reload file watcher error: {e}"),
+            }
+        }
+    });
+
+    Reload {
+        watcher: Mutex::new(watcher),
+        index: Mutex::new(HashMap::new()),
+        watched_dirs: Mutex::new(HashSet::new()),
+        subscribers: Mutex::new(Vec::new()),
+    }
+});
+
+fn handle_event(event: notify::Event) {
+    if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+        return;
+    }
+    for path in &event.paths {
+        // directory events fire for every file in the watched directory, not just ones we care
+        // about, and a rename-over-the-original save means the path notify hands back may not be
+        // byte-identical to what we watched; canonicalize both sides before comparing
+        let Ok(path) = path.canonicalize() else { continue };
+        let hashes = RELOAD.index.lock().unwrap().get(&path).cloned();
+        let Some(hashes) = hashes else { continue };
+        for hash in hashes {
+            crate::mgr::evict(hash);
+            for sub in RELOAD.subscribers.lock().unwrap().iter() {
+                sub(hash);
+            }
+        }
+    }
+}
+
+/// Enable or disable hot-reload tracking for assets loaded from this point on
+///
+/// Disabled by default, since it spins up a filesystem watcher thread. Assets already cached
+/// before this is enabled aren't retroactively tracked; call `load` on them again (a cache hit is
+/// enough) to start watching. Editing an untracked file evicts nothing.
+pub fn set_hot_reload(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Subscribe to reload notifications
+///
+/// `callback` is invoked, on the watcher's background thread, with the cache hash of each asset
+/// evicted by a filesystem change. Look up any [`crate::Handle`]s you derived from that path and
+/// re-upload them (e.g. to the GPU); the cache itself only reparses on the next `load`.
+pub fn on_reload(callback: impl Fn(u64) + Send + Sync + 'static) {
+    RELOAD.subscribers.lock().unwrap().push(Box::new(callback));
+}
+
+/// Record that `hash` is backed by `path`'s filesystem file, starting a watch on its containing
+/// directory if this is the first asset that depends on that directory
+///
+/// No-op if hot-reload isn't enabled or `path` has no backing file (e.g. a remote asset).
+pub(crate) fn track(path: &Path, hash: u64) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let Some(file) = path.backing_file() else {
+        return;
+    };
+    let Ok(file) = file.canonicalize() else {
+        warn!("failed to canonicalize '{}' for hot-reload", file.display());
+        return;
+    };
+
+    let dir = file.parent().map(PathBuf::from).unwrap_or_else(|| PathBuf::from("."));
+    let mut watched_dirs = RELOAD.watched_dirs.lock().unwrap();
+    if watched_dirs.insert(dir.clone()) {
+        if let Err(e) = RELOAD
+            .watcher
+            .lock()
+            .unwrap()
+            .watch(&dir, RecursiveMode::NonRecursive)
+        {
+            warn!("failed to watch '{}' for hot-reload: {e}", dir.display());
+            watched_dirs.remove(&dir);
+        }
+    }
+    drop(watched_dirs);
+
+    let mut index = RELOAD.index.lock().unwrap();
+    let hashes = index.entry(file).or_insert_with(Vec::new);
+    if !hashes.contains(&hash) {
+        hashes.push(hash);
+    }
+}
@@ -0,0 +1,70 @@
+//! Pluggable URI scheme registry
+//!
+//! [`Path`](crate::Path)'s `file:` and `bin:` schemes are handled directly, but any other scheme
+//! can be served by registering a [`ReaderFactory`] for it with [`register_scheme`]. `http` and
+//! `https` are registered this way out of the box, streaming a remote asset into memory so
+//! `load("https://.../model.obj", ObjScene)` works through the same `Format` machinery, and the
+//! same cache, as a local asset.
+
+use std::{
+    collections::HashMap,
+    io::{Cursor, Read},
+    sync::{Arc, RwLock},
+};
+
+use once_cell::sync::Lazy;
+use reerror::{conversions::not_found, throw, Result};
+
+use crate::path::AssetReader;
+
+/// Produces a reader for a URI under some registered scheme
+pub trait ReaderFactory: Send + Sync {
+    fn open(&self, uri: &str) -> Result<Box<dyn AssetReader>>;
+}
+
+impl<F> ReaderFactory for F
+where
+    F: Fn(&str) -> Result<Box<dyn AssetReader>> + Send + Sync,
+{
+    fn open(&self, uri: &str) -> Result<Box<dyn AssetReader>> {
+        self(uri)
+    }
+}
+
+static REGISTRY: Lazy<RwLock<HashMap<String, Arc<dyn ReaderFactory>>>> = Lazy::new(|| {
+    let mut registry: HashMap<String, Arc<dyn ReaderFactory>> = HashMap::new();
+    let http: Arc<dyn ReaderFactory> = Arc::new(http_reader as fn(&str) -> Result<Box<dyn AssetReader>>);
+    registry.insert("http".to_string(), Arc::clone(&http));
+    registry.insert("https".to_string(), http);
+    RwLock::new(registry)
+});
+
+/// Register a reader factory for a custom URI scheme, e.g. so `load("myscheme:...", Txt)` works
+pub fn register_scheme(scheme: &str, factory: impl ReaderFactory + 'static) {
+    REGISTRY
+        .write()
+        .unwrap()
+        .insert(scheme.to_ascii_lowercase(), Arc::new(factory));
+}
+
+pub(crate) fn is_registered(scheme: &str) -> bool {
+    REGISTRY.read().unwrap().contains_key(scheme)
+}
+
+pub(crate) fn open(scheme: &str, uri: &str) -> Result<Box<dyn AssetReader>> {
+    let factory = REGISTRY.read().unwrap().get(scheme).cloned();
+    match factory {
+        Some(factory) => factory.open(uri),
+        None => Err(not_found(format!("no reader registered for scheme '{scheme}'"))),
+    }
+}
+
+fn http_reader(uri: &str) -> Result<Box<dyn AssetReader>> {
+    let response = throw!(ureq::get(uri).call(), "requesting {uri}");
+    let mut buffer = Vec::new();
+    throw!(
+        response.into_reader().read_to_end(&mut buffer),
+        "reading response body from {uri}"
+    );
+    Ok(Box::new(Cursor::new(buffer)))
+}